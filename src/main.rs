@@ -4,7 +4,12 @@
 // Feel free to delete this line.
 #![allow(clippy::too_many_arguments, clippy::type_complexity)]
 
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
 use bevy::{
+    core::FrameCount,
     prelude::*,
     reflect::TypeUuid,
     render::{
@@ -12,34 +17,51 @@ use bevy::{
         render_asset::RenderAssets,
         render_graph::{self, RenderGraph, SlotInfo, SlotType},
         render_resource::*,
-        renderer::{RenderContext, RenderDevice},
+        renderer::{RenderContext, RenderDevice, RenderQueue},
         view::{ViewUniform, ViewUniforms, ViewUniformOffset, ExtractedView},
-        RenderApp, RenderSet,
+        Extract, ExtractSchedule, MainWorld, RenderApp, RenderSet,
     },
-    window::WindowPlugin, core_pipeline::core_3d,
+    window::{PrimaryWindow, WindowPlugin, WindowResized}, core_pipeline::core_3d,
 };
 // Moo. "clone on write", ie keep a ref until change is needed, then clone (https://doc.rust-lang.org/std/borrow/enum.Cow.html)
 use std::borrow::Cow;
 
-// Compute shader dimensions
-
-// Total threads X*Y
-const SIZE: (u32, u32) = (640, 480);
-// Threads per group X*X
-const WORKGROUP_SIZE: u32 = 8;
+// Runtime resolution/workgroup-size settings for a `ComputePlugin<T>`,
+// extracted into the render world so the dispatch math and bind group
+// layout can be driven from the main world instead of baked in as consts.
+#[derive(Resource, Clone)]
+pub struct ComputeShaderConfig<T: ComputeShader> {
+    pub resolution: UVec2,
+    pub workgroup_size: u32,
+    /// Format of the ping-pong render targets, passed to `T::bind_group_layout`
+    /// so a user can swap in e.g. `Rgba16Float` for an HDR target without
+    /// forking the plugin. The images themselves still have to be created
+    /// with a matching format (see `new_storage_image`'s caller).
+    pub texture_format: TextureFormat,
+    _marker: PhantomData<T>,
+}
 
-// Types
+impl<T: ComputeShader> Default for ComputeShaderConfig<T> {
+    fn default() -> Self {
+        Self {
+            resolution: UVec2::new(640, 480),
+            workgroup_size: 8,
+            texture_format: TextureFormat::Rgba8Unorm,
+            _marker: PhantomData,
+        }
+    }
+}
 
-// Custom struct for tracking the render target
-// Derives clone so its internals are deep copied,
-// Deref to get the Image from handle (struct must be single-item for this!)
-// and ExtractResource in order to be able to extract the image from bevy's main/game "world" to its render "world"
-#[derive(Resource, Clone, Deref, ExtractResource)]
-struct MyComputeShaderRenderTarget(Handle<Image>);
+impl<T: ComputeShader> ExtractResource for ComputeShaderConfig<T> {
+    type Source = Self;
 
-// Custom struct containing bind group of resources for our shader.
-#[derive(Resource)]
-struct MyComputeShaderBindGroup(BindGroup);
+    fn extract_resource(source: &Self::Source) -> Self {
+        // `ComputeShaderConfig<T>` only derives `Clone` (`T` is bound
+        // `ComputeShader: ... + Clone`, not `Copy`), so `*source` doesn't
+        // type-check here - clone instead.
+        source.clone()
+    }
+}
 
 #[derive(Component)]
 struct MainCamera;
@@ -63,36 +85,87 @@ fn main() {
                 }),
         )
         .add_plugin(MaterialPlugin::<CustomMaterial>::default())
-        .add_plugin(MyComputeShaderPlugin)
+        .add_plugin(ComputePlugin::<MyComputeShader>::default())
         .add_startup_system(setup)
         .add_system(rotate_camera)
+        .add_system(swap_presented_texture)
+        .add_system(handle_window_resize)
         .run();
 }
 
-fn setup(
-    mut commands: Commands,
-    //asset_server: Res<AssetServer>,
-    mut images: ResMut<Assets<Image>>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut custom_materials: ResMut<Assets<CustomMaterial>>,
-    mut standard_materials: ResMut<Assets<StandardMaterial>>,
-) {
+// Resource tracking the `CustomMaterial` instance that presents the compute
+// shader's output, so `swap_presented_texture` can flip its source texture.
+#[derive(Resource)]
+struct PresentedMaterial(Handle<CustomMaterial>);
 
-    // Create main presentation texture and compute render target resource...
+fn new_storage_image(
+    images: &mut Assets<Image>,
+    resolution: UVec2,
+    texture_format: TextureFormat,
+) -> Handle<Image> {
     let mut image = Image::new_fill(
         Extent3d {
-            width: SIZE.0,
-            height: SIZE.1,
+            width: resolution.x,
+            height: resolution.y,
             depth_or_array_layers: 1,
         },
         TextureDimension::D2,
         &[0, 0, 0, 255],
-        TextureFormat::Rgba8Unorm,
+        texture_format,
     );
     image.texture_descriptor.usage =
         TextureUsages::COPY_DST | TextureUsages::STORAGE_BINDING | TextureUsages::TEXTURE_BINDING;
-    // ...and add it to our image asset server
-    let image = images.add(image);
+    images.add(image)
+}
+
+// Reallocate the compute render targets whenever the primary window resizes,
+// so the shader keeps covering the whole window instead of a stale corner of
+// it. Ignores resolutions that aren't an actual change (e.g. redundant
+// resize events bevy can fire on some platforms).
+fn handle_window_resize(
+    mut resize_events: EventReader<WindowResized>,
+    mut config: ResMut<ComputeShaderConfig<MyComputeShader>>,
+    mut images: ResMut<Assets<Image>>,
+    render_target: Res<MyComputeShader>,
+) {
+    let Some(event) = resize_events.iter().last() else {
+        return;
+    };
+    let resolution = UVec2::new(event.width.max(1.0) as u32, event.height.max(1.0) as u32);
+    if resolution == config.resolution {
+        return;
+    }
+    config.resolution = resolution;
+
+    let extent = Extent3d {
+        width: resolution.x,
+        height: resolution.y,
+        depth_or_array_layers: 1,
+    };
+    for handle in &render_target.textures {
+        if let Some(image) = images.get_mut(handle) {
+            image.resize(extent);
+        }
+    }
+}
+
+fn setup(
+    mut commands: Commands,
+    //asset_server: Res<AssetServer>,
+    mut images: ResMut<Assets<Image>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut custom_materials: ResMut<Assets<CustomMaterial>>,
+    mut standard_materials: ResMut<Assets<StandardMaterial>>,
+    config: Res<ComputeShaderConfig<MyComputeShader>>,
+) {
+
+    // Create the two ping-pong render targets our compute shader reads/writes.
+    // Each frame one is bound read-only as "previous" and the other write-only
+    // as "next", so a neighbor-sampling kernel never races against itself.
+    let textures = [
+        new_storage_image(&mut images, config.resolution, config.texture_format),
+        new_storage_image(&mut images, config.resolution, config.texture_format),
+    ];
 
     commands.spawn(PbrBundle {
         mesh: meshes.add(shape::Plane::from_size(5.0).into()),
@@ -104,18 +177,22 @@ fn setup(
         ..default()
     });
 
+    let custom_material = custom_materials.add(CustomMaterial {
+        color: Color::WHITE,
+        texture: textures[0].clone(),
+    });
+
     commands.spawn(MaterialMeshBundle {
         mesh: meshes.add(Mesh::from(shape::Cube { size: 1.0 })),
         transform: Transform::from_xyz(0.0, 0.5, 0.0),
-        material: custom_materials.add(CustomMaterial {
-            color: Color::WHITE,
-            texture: image.clone(),
-        }),
+        material: custom_material.clone(),
         ..default()
     });
+    commands.insert_resource(PresentedMaterial(custom_material));
 
-    // Add image handle as a resource (of our type) to track
-    commands.insert_resource(MyComputeShaderRenderTarget(image));
+    // Add the compute shader's render target as the resource our ComputeShader
+    // impl is keyed on. This is what gets extracted into the render world.
+    commands.insert_resource(MyComputeShader { textures });
 
     // camera
     commands.spawn((
@@ -127,6 +204,22 @@ fn setup(
     ));
 }
 
+// Flip `CustomMaterial`'s source texture to whichever buffer the compute
+// shader wrote to most recently. Both this system and the render-world bind
+// group queueing derive the same parity from `FrameCount`, so no feedback
+// channel from the render world back to the main world is needed.
+fn swap_presented_texture(
+    frame_count: Res<FrameCount>,
+    render_target: Res<MyComputeShader>,
+    presented: Res<PresentedMaterial>,
+    mut custom_materials: ResMut<Assets<CustomMaterial>>,
+) {
+    let written = render_target.written_index(frame_count.0);
+    if let Some(material) = custom_materials.get_mut(&presented.0) {
+        material.texture = render_target.textures[written].clone();
+    }
+}
+
 fn rotate_camera(mut camera: Query<&mut Transform, With<MainCamera>>, time: Res<Time>) {
     let cam_transform = camera.single_mut().into_inner();
 
@@ -157,10 +250,13 @@ impl Material for CustomMaterial {
 }
 
 // ----------------------------------------------------------------------------
-// Compute shader plugin
-// Here is where we encapsulate all our compute shader stuff.
-// It instantiates our pipeline object and adds our render
-// node to the graph.
+// Generic compute shader plugin
+// Instead of hard-coding one shader/bind-group/render-target, the plugin is
+// parameterized over a `ComputeShader` impl the same way `MaterialPlugin<M>`
+// is parameterized over a `Material` impl. A crate user attaches their own
+// compute pass by implementing the trait on their own resource type and
+// registering `ComputePlugin::<TheirType>::default()` - no copy-pasting this
+// file required.
 //
 //               [Resources]
 //                    |
@@ -176,35 +272,121 @@ impl Material for CustomMaterial {
 //  Draw Render Graph -> Draw Render Node -> Draw Pipeline -> Draw Shader
 // ----------------------------------------------------------------------------
 
-pub struct MyComputeShaderPlugin;
+/// Everything a user needs to supply to plug a compute pass into `ComputePlugin<T>`.
+///
+/// `T` itself lives as a resource in both worlds (extracted via `ExtractResource`,
+/// same as a `Material` asset handle) and is responsible for producing the bind
+/// group its own shader expects.
+///
+/// `bind_group_layout`/`bind_group` are hand-written rather than derived via
+/// `AsBindGroup`: this plugin's bind group mixes a dynamic-offset view
+/// uniform and a ping-pong pair picked at dispatch time by `frame_count`,
+/// neither of which `AsBindGroup`'s derive macro (built for a fixed set of
+/// per-instance fields) expresses. An `AsBindGroup`-driven version of this
+/// plugin was explored separately and dropped - see the removal of
+/// `src/compute.rs` - rather than carried forward here.
+pub trait ComputeShader: Resource + ExtractResource + Clone {
+    /// Location of the compute shader on disk (or an embedded/weak handle).
+    fn shader() -> ShaderRef;
+    /// Entry points to build one `ComputePipeline` per, run in order.
+    fn entry_points() -> &'static [&'static str];
+    /// Entry points (a subset of `entry_points()`, matched by name) that
+    /// should dispatch only once - the first frame they're confirmed
+    /// compiled - instead of every frame. For a seed/init pass that has to
+    /// run before a feedback `update` pass can read its own previous
+    /// output, letting it re-run every frame would just have `update`
+    /// immediately clobber it. Defaults to none, so impls that don't need
+    /// this keep the old "dispatch every ready entry point every frame"
+    /// behavior.
+    fn one_shot_entry_points() -> &'static [&'static str] {
+        &[]
+    }
+    /// Layout describing the bind group `bind_group` below must produce.
+    /// `texture_format` is `ComputeShaderConfig<T>::texture_format`, so an
+    /// impl's storage-texture bindings follow the same runtime-configurable
+    /// format its render targets are actually created with.
+    fn bind_group_layout(render_device: &RenderDevice, texture_format: TextureFormat) -> BindGroupLayout;
+    /// Build the bind group for this frame's dispatch. `frame_count` lets
+    /// impls that ping-pong between buffers pick the same "read the
+    /// previous frame, write the next one" pairing the node will dispatch
+    /// against, without needing a render-world -> main-world feedback path.
+    fn bind_group(
+        &self,
+        render_device: &RenderDevice,
+        layout: &BindGroupLayout,
+        gpu_images: &RenderAssets<Image>,
+        view_uniforms: &ViewUniforms,
+        frame_count: u32,
+        compute_uniforms: &ComputeUniformBuffer,
+    ) -> Option<BindGroup>;
+}
+
+pub struct ComputePlugin<T: ComputeShader>(PhantomData<T>);
+
+impl<T: ComputeShader> Default for ComputePlugin<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
 
-impl Plugin for MyComputeShaderPlugin {
+impl<T: ComputeShader> Plugin for ComputePlugin<T> {
     // Plugin setup on app startup
     fn build(&self, app: &mut App) {
-        // Extract the render target on which the compute shader needs access to.
-        // From main world to render world.
-        app.add_plugin(ExtractResourcePlugin::<MyComputeShaderRenderTarget>::default());
+        // Extract the user's resource (render target handles, params, ...)
+        // from main world to render world.
+        app.add_plugin(ExtractResourcePlugin::<T>::default());
+
+        // Minimal loading-screen hook: a main-world resource the user can
+        // check to find out whether our pipelines have finished compiling.
+        app.init_resource::<PipelinesReady<T>>();
+
+        // Let a user flip runtime pipeline variants by mutating this resource.
+        app.init_resource::<ComputeShaderKey<T>>();
+        app.add_plugin(ExtractResourcePlugin::<ComputeShaderKey<T>>::default());
+
+        // Resolution/workgroup-size are runtime-configurable instead of
+        // compile-time consts, and follow window resizes (see `handle_window_resize`).
+        app.init_resource::<ComputeShaderConfig<T>>();
+        app.add_plugin(ExtractResourcePlugin::<ComputeShaderConfig<T>>::default());
+
+        // Cursor position, normalized to the window, for shaders that want
+        // to react to it (e.g. painting/poking a simulation).
+        app.init_resource::<ComputeCursor<T>>();
+        app.add_plugin(ExtractResourcePlugin::<ComputeCursor<T>>::default());
+        app.add_system(update_compute_cursor::<T>);
+
+        // Read the texture format the user configured before handing off to
+        // the render app - see `ComputePipeline::new`.
+        let texture_format = app.world.resource::<ComputeShaderConfig<T>>().texture_format;
 
         // Create our custom render pipeline and a bind group stage
         // Pipeline describes stages (shaders) of a custom graphics pipeline.
         // Bind groups binds resources to the shaders.
         let render_app = app.sub_app_mut(RenderApp); // fetch sub app "RenderApp"
+        let pipeline = ComputePipeline::<T>::new(&render_app.world, texture_format);
         render_app
-            .init_resource::<MyComputeShaderPipeline>()
-            .add_system(queue_bind_group.in_set(RenderSet::Queue));
+            .insert_resource(pipeline)
+            .init_resource::<ComputeUniformBuffer>()
+            .add_system_to_schedule(ExtractSchedule, extract_frame_count)
+            .add_system_to_schedule(ExtractSchedule, extract_time)
+            .add_system(queue_bind_group::<T>.in_set(RenderSet::Queue))
+            // `MainWorld` is only present in the render world while the
+            // extract schedule runs, so this has to live there.
+            .add_system_to_schedule(ExtractSchedule, update_pipelines_ready::<T>)
+            .add_system(prepare_compute_uniforms::<T>.in_set(RenderSet::Prepare));
 
         // Create render graph node for our shader. It defines the dependencies our shader and its resources has to others.
-        let node = MyComputeShaderNode::new(&mut render_app.world);
+        let node = ComputeNode::<T>::new(&mut render_app.world);
         // Get the scheduling graph to add our node to.
         let mut render_graph = render_app.world.resource_mut::<RenderGraph>();
-        const MY_COMPUTE_NODE_NAME: &str = "my_compute_node";
+        let node_name = node_name::<T>();
 
         // Make the node
-        render_graph.add_node(MY_COMPUTE_NODE_NAME, node);
+        render_graph.add_node(node_name.clone(), node);
         // Schedule node to run before the camera node, check for OK with unwrap (panics if not)
-        
+
         render_graph.add_node_edge(
-            MY_COMPUTE_NODE_NAME,
+            node_name.clone(),
             bevy::render::main_graph::node::CAMERA_DRIVER,
         );
         let input_node_id = render_graph.set_input(vec![SlotInfo::new(
@@ -214,56 +396,115 @@ impl Plugin for MyComputeShaderPlugin {
         render_graph.add_slot_edge(
             input_node_id,
             core_3d::graph::input::VIEW_ENTITY,
-            MY_COMPUTE_NODE_NAME,
-            MyComputeShaderNode::IN_VIEW,
+            node_name,
+            ComputeNode::<T>::IN_VIEW,
         )
     }
 }
 
+// Every `ComputePlugin<T>` needs its own graph node name so multiple compute
+// passes can coexist without clobbering each other.
+fn node_name<T: ComputeShader>() -> Cow<'static, str> {
+    Cow::from(format!("compute_node_{}", std::any::type_name::<T>()))
+}
+
+// `FrameCount` is a main-world resource owned by bevy_core, not our own
+// type, so it can't pick up `ExtractResource` the way
+// `ComputeShaderConfig<T>`/`ComputeShaderKey<T>` do - copy it into the
+// render world by hand instead, so `queue_bind_group`'s ping-pong parity
+// actually tracks the main world's frame count instead of panicking (or
+// reading a stuck default) on a resource that was never extracted.
+fn extract_frame_count(mut commands: Commands, frame_count: Extract<Res<FrameCount>>) {
+    commands.insert_resource(FrameCount(frame_count.0));
+}
+
+// Same story as `extract_frame_count`, for `Time`: without this,
+// `prepare_compute_uniforms`'s `Res<Time>` either panics on the first frame
+// or reads whatever `Time` was last extracted for something else, so the
+// shader's `time`/`delta_time` uniforms never actually advance.
+fn extract_time(mut commands: Commands, time: Extract<Res<Time>>) {
+    commands.insert_resource(time.clone());
+}
+
 // -------------------------------------------------------------
 // Bind group queueing
 // Bindings for shader resources.
 // -------------------------------------------------------------
 
-// Our bind group enqueueing function/system that is added to the Bevy "Queue" render stage in the plugin setup.
-// Queues the bind group that exist in the pipeline
-fn queue_bind_group(
+// Our bind group enqueueing system that is added to the Bevy "Queue" render stage in the plugin setup.
+// Queues the bind group `T` produces for its own shader.
+fn queue_bind_group<T: ComputeShader>(
     mut commands: Commands,
-    pipeline: Res<MyComputeShaderPipeline>,
+    pipeline: Res<ComputePipeline<T>>,
     gpu_images: Res<RenderAssets<Image>>,
-    render_target: Res<MyComputeShaderRenderTarget>,
+    shader_res: Res<T>,
     view_uniforms: Res<ViewUniforms>,
     device: Res<RenderDevice>,
+    frame_count: Res<FrameCount>,
+    compute_uniforms: Res<ComputeUniformBuffer>,
 ) {
-    if let (
-        Some(view_binding),
-        Some(render_target_view),
-        ) = (
-        view_uniforms.uniforms.binding(),
-        gpu_images.get(&*render_target),
+    if let Some(bind_group) = shader_res.bind_group(
+        &device,
+        &pipeline.bind_group_layout,
+        &gpu_images,
+        &view_uniforms,
+        frame_count.0,
+        &compute_uniforms,
     ) {
+        commands.insert_resource(ComputeBindGroup::<T>(bind_group, PhantomData))
+    }
+}
 
-        // Fetch gpu view of our render target.
-        // We can use * on render_target to get the handle to borrow as MyComputeShaderRenderTarget derives Deref (otherwise use .0).
-        // let render_target_view = &gpu_images[&*render_target];
+// Wraps the bind group `T::bind_group` produced, generic over `T` so several
+// `ComputePlugin<T>` instances don't collide on the same resource type.
+#[derive(Resource)]
+struct ComputeBindGroup<T: ComputeShader>(BindGroup, PhantomData<T>);
 
-        let view_entry = BindGroupEntry {
-            binding: 0,
-            resource: view_binding.clone(),
-        };
+// -------------------------------------------------------------
+// Loading-screen hook
+// -------------------------------------------------------------
 
-        let texture_entry = BindGroupEntry {
-            binding: 1,
-            resource: BindingResource::TextureView(&render_target_view.texture_view),
-        };
+/// Main-world resource telling a user whether every pipeline `ComputePlugin<T>`
+/// queued has finished compiling. With async pipeline compilation a pipeline
+/// can sit in `CachedPipelineState::Creating` for several frames, so gating
+/// gameplay/UI on this instead of just "has the app started" avoids showing
+/// garbage frames while the shader is still being built.
+#[derive(Resource)]
+pub struct PipelinesReady<T: ComputeShader>(bool, PhantomData<T>);
 
-        // Bind the view to a new bind group (I assume if we have more resources we add them to the same group as make sense based on lifetimes)
-        let bind_group = device.create_bind_group(&BindGroupDescriptor {
-            label: Some("my_rendertexture_bindgroup"),
-            layout: &pipeline.texture_bind_group_layout,
-            entries: &[view_entry, texture_entry],
-        });
-        commands.insert_resource(MyComputeShaderBindGroup(bind_group))
+impl<T: ComputeShader> Default for PipelinesReady<T> {
+    fn default() -> Self {
+        Self(false, PhantomData)
+    }
+}
+
+impl<T: ComputeShader> PipelinesReady<T> {
+    pub fn get(&self) -> bool {
+        self.0
+    }
+}
+
+// Compares our pipeline ids against `PipelineCache::waiting_pipelines` and
+// pushes the result back into the main world's `PipelinesReady<T>`. There's
+// no built-in render -> main channel for this, so we reach into `MainWorld`
+// directly, the same trick Bevy's own render-to-texture examples use.
+fn update_pipelines_ready<T: ComputeShader>(
+    pipeline_cache: Res<PipelineCache>,
+    pipeline: Res<ComputePipeline<T>>,
+    mut main_world: ResMut<MainWorld>,
+) {
+    let still_waiting = pipeline_cache.waiting_pipelines().any(|id| {
+        pipeline
+            .variants
+            .values()
+            .any(|ids| ids.iter().any(|ours| *ours == id))
+    });
+
+    // Nothing queued yet (the node queues variants lazily) means not ready.
+    let any_queued = pipeline.variants.values().any(|ids| !ids.is_empty());
+
+    if let Some(mut ready) = main_world.get_resource_mut::<PipelinesReady<T>>() {
+        ready.0 = any_queued && !still_waiting;
     }
 }
 
@@ -272,103 +513,209 @@ fn queue_bind_group(
 // Contains information on what shaders to run and their bindings.
 // -------------------------------------------------------------
 
-// Custom struct defining the pipeline, contains references to the bind groups that binds the resources needed
-// and the pipelines for initializing and updating.
+bitflags::bitflags! {
+    /// Runtime feature toggles for a compute pipeline variant - the compute
+    /// equivalent of the `shader_defs` bitflags Bevy's other `Specialize*`
+    /// pipelines use to branch without hand-authoring a separate shader file.
+    #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+    pub struct ComputePipelineKey: u32 {
+        const NONE = 0;
+        const DEBUG_VIS = 1 << 0;
+    }
+}
+
+impl ComputePipelineKey {
+    fn shader_defs(&self) -> Vec<ShaderDefVal> {
+        let mut defs = Vec::new();
+        if self.contains(Self::DEBUG_VIS) {
+            defs.push("DEBUG_VIS".into());
+        }
+        defs
+    }
+}
+
+// Main-world resource picking which `ComputePipelineKey` variant `T`'s
+// pipelines should run with. Extracted into the render world so a user can
+// flip e.g. a debug-visualization branch at runtime by mutating this.
+#[derive(Resource, Clone, Copy, ExtractResource)]
+pub struct ComputeShaderKey<T: ComputeShader>(pub ComputePipelineKey, PhantomData<T>);
+
+impl<T: ComputeShader> Default for ComputeShaderKey<T> {
+    fn default() -> Self {
+        Self(ComputePipelineKey::NONE, PhantomData)
+    }
+}
+
+// Generic pipeline resource: lazily builds and caches one
+// `CachedComputePipelineId` per entry point `T::entry_points()` declares, for
+// each `ComputePipelineKey` variant actually requested so far.
 #[derive(Resource)]
-pub struct MyComputeShaderPipeline {
-    texture_bind_group_layout: BindGroupLayout,
-    init_pipeline_id: CachedComputePipelineId,
-    update_pipeline_id: CachedComputePipelineId,
+pub struct ComputePipeline<T: ComputeShader> {
+    bind_group_layout: BindGroupLayout,
+    shader: Handle<Shader>,
+    variants: HashMap<ComputePipelineKey, Vec<CachedComputePipelineId>>,
+    _marker: PhantomData<T>,
 }
 
-// The uniform struct extracted from Camera.
-// Will be available for use in the compute shader.
-#[derive(Component, ShaderType, Clone)]
+impl<T: ComputeShader> ComputePipeline<T> {
+    // Returns the pipeline ids for `key`, queuing them with the pipeline
+    // cache the first time this particular key is requested.
+    fn pipeline_ids_for(
+        &mut self,
+        pipeline_cache: &PipelineCache,
+        key: ComputePipelineKey,
+    ) -> Vec<CachedComputePipelineId> {
+        self.variants
+            .entry(key)
+            .or_insert_with(|| {
+                T::entry_points()
+                    .iter()
+                    .map(|entry_point| {
+                        pipeline_cache.queue_compute_pipeline(ComputePipelineDescriptor {
+                            label: Some(Cow::from(format!(
+                                "compute_pipeline_{entry_point}_{key:?}"
+                            ))),
+                            layout: vec![self.bind_group_layout.clone()],
+                            push_constant_ranges: Vec::new(),
+                            shader: self.shader.clone(),
+                            shader_defs: key.shader_defs(),
+                            entry_point: Cow::from(*entry_point),
+                        })
+                    })
+                    .collect()
+            })
+            .clone()
+    }
+}
+
+// Per-frame uniform data every compute shader can bind to know the compute
+// target's resolution (`viewport.zw`), correct for the camera's aspect
+// ratio, animate over time, and react to the cursor.
+#[derive(ShaderType, Clone, Default)]
 pub struct ComputeUniforms {
     pub viewport: Vec4,
     pub aspect: f32,
+    pub time: f32,
+    pub delta_time: f32,
+    pub frame: u32,
+    pub cursor_position: Vec2,
 }
 
-// Implement the FromWorld trait on our pipeline, which allows it to
-// initialize from a given world context when created as a resource to the RenderApp
-impl FromWorld for MyComputeShaderPipeline {
-    // Override the from_world function to do setups when given world context
-    // Returns an instance of self: an initialized MyComputeShaderPipeline.
-    fn from_world(world: &mut World) -> Self {
-        // Setup members of struct
-        /*let uniform_layout = BindGroupLayoutEntry {
-            binding: 0,
-            visibility: ShaderStages::COMPUTE,
-            ty: BindingType::Buffer {
-                ty: BufferBindingType::Uniform,
-                has_dynamic_offset: false,
-                min_binding_size: Some(ComputeUniforms::min_size()),
-            },
-            count: None,
-        };*/
+// Main-world resource tracking the primary window's cursor position,
+// normalized to 0..1, extracted into the render world each frame so
+// `prepare_compute_uniforms` can fold it into `ComputeUniforms`. Generic
+// over `T` so several `ComputePlugin<T>` instances don't collide on the
+// same resource type.
+#[derive(Resource, Clone, Copy)]
+pub struct ComputeCursor<T: ComputeShader>(pub Vec2, PhantomData<T>);
+
+impl<T: ComputeShader> Default for ComputeCursor<T> {
+    fn default() -> Self {
+        Self(Vec2::ZERO, PhantomData)
+    }
+}
 
-        let view_layout = BindGroupLayoutEntry {
-            binding: 0,
-            visibility: ShaderStages::COMPUTE,
-            ty: BindingType::Buffer {
-                ty: BufferBindingType::Uniform,
-                has_dynamic_offset: true,
-                min_binding_size: Some(ViewUniform::min_size()),
-            },
-            count: None,
-        };
+impl<T: ComputeShader> ExtractResource for ComputeCursor<T> {
+    type Source = Self;
 
-        let texture_layout = BindGroupLayoutEntry {
-            binding: 1,
-            visibility: ShaderStages::COMPUTE,
-            ty: BindingType::StorageTexture {
-                access: StorageTextureAccess::ReadWrite,
-                format: TextureFormat::Rgba8Unorm,
-                view_dimension: TextureViewDimension::D2,
-            },
-            count: None,
-        };
-        // Define the layout of the bind group, ie. the members to bind to the shader.
-        // This layout is referenced when queuing the bind group to the shader.
+    fn extract_resource(source: &Self) -> Self {
+        // The derived `Copy` only holds for `T: Copy`, which `ComputeShader`
+        // doesn't require, so clone instead of `*source`.
+        source.clone()
+    }
+}
+
+fn update_compute_cursor<T: ComputeShader>(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut cursor: ResMut<ComputeCursor<T>>,
+) {
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+    if let Some(position) = window.cursor_position() {
+        cursor.0 = Vec2::new(
+            position.x / window.width().max(1.0),
+            position.y / window.height().max(1.0),
+        );
+    }
+}
+
+// Render-world resource owning the GPU-side buffer `ComputeUniforms` is
+// uploaded into. `UniformBuffer` takes care of (re)creating the buffer as
+// the struct's size changes and queuing the `write_buffer` call.
+#[derive(Resource, Default)]
+struct ComputeUniformBuffer(UniformBuffer<ComputeUniforms>);
+
+// Gather time, frame count, cursor position, the compute target's resolution
+// (as `viewport`) and the first view's aspect ratio each frame and upload
+// them into `ComputeUniformBuffer`,
+// so compute shaders can animate, react to the cursor, and correct for
+// aspect ratio the way the standard animated-shader examples do.
+fn prepare_compute_uniforms<T: ComputeShader>(
+    time: Res<Time>,
+    frame_count: Res<FrameCount>,
+    cursor: Res<ComputeCursor<T>>,
+    config: Res<ComputeShaderConfig<T>>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    mut uniform_buffer: ResMut<ComputeUniformBuffer>,
+    views: Query<&ExtractedView>,
+) {
+    let aspect = views
+        .iter()
+        .next()
+        .map(|view| view.viewport.z as f32 / view.viewport.w.max(1) as f32)
+        .unwrap_or(1.0);
+
+    uniform_buffer.0.set(ComputeUniforms {
+        viewport: Vec4::new(
+            0.0,
+            0.0,
+            config.resolution.x as f32,
+            config.resolution.y as f32,
+        ),
+        aspect,
+        time: time.elapsed_seconds(),
+        delta_time: time.delta_seconds(),
+        frame: frame_count.0,
+        cursor_position: cursor.0,
+    });
+    uniform_buffer
+        .0
+        .write_buffer(&render_device, &render_queue);
+}
+
+impl<T: ComputeShader> ComputePipeline<T> {
+    // Built explicitly (instead of via `FromWorld`/`init_resource`) because
+    // `T::bind_group_layout` needs `texture_format` - the render world
+    // doesn't have `ComputeShaderConfig<T>` extracted into it yet at plugin
+    // build time, so the caller reads it from the main world instead and
+    // passes it in.
+    fn new(world: &World, texture_format: TextureFormat) -> Self {
         let bind_group_layout =
-            world
-                .resource::<RenderDevice>()
-                .create_bind_group_layout(&BindGroupLayoutDescriptor {
-                    label: Some("my_rendertexture_bindgroup_layout"),
-                    entries: &[view_layout, texture_layout],
-                });
+            T::bind_group_layout(world.resource::<RenderDevice>(), texture_format);
+
         // Load the shader
-        let shader = world
-            .resource::<AssetServer>()
-            .load("shaders/my_compute_shader.wgsl");
-        // Create sub pipelines for our pipeline. They are created through the pipeline cache resource, keeping them cached, for efficient rendering.
-        let pipeline_cache = world.resource::<PipelineCache>();
-        let init_pipeline_id = pipeline_cache.queue_compute_pipeline(ComputePipelineDescriptor {
-            label: Some(Cow::from("my_compute_pipeline_init")),
-            layout: vec![bind_group_layout.clone()],
-            push_constant_ranges: Vec::new(),
-            shader: shader.clone(),
-            shader_defs: vec![],
-            entry_point: Cow::from("init"),
-        });
-        let update_pipeline_id = pipeline_cache.queue_compute_pipeline(ComputePipelineDescriptor {
-            label: Some(Cow::from("my_compute_pipeline_update")),
-            layout: vec![bind_group_layout.clone()],
-            push_constant_ranges: Vec::new(),
+        let shader = world.resource::<AssetServer>().load(shader_path::<T>());
+
+        // Pipeline variants are queued lazily the first time a given
+        // `ComputePipelineKey` is actually requested - see `pipeline_ids_for`.
+        ComputePipeline {
+            bind_group_layout,
             shader,
-            shader_defs: vec![],
-            entry_point: Cow::from("update"),
-        });
-
-        // Construct pipeline object and return
-        MyComputeShaderPipeline {
-            texture_bind_group_layout: bind_group_layout,
-            init_pipeline_id: init_pipeline_id,
-            update_pipeline_id: update_pipeline_id,
+            variants: HashMap::new(),
+            _marker: PhantomData,
         }
     }
 }
 
+fn shader_path<T: ComputeShader>() -> Cow<'static, str> {
+    match T::shader() {
+        ShaderRef::Path(path) => Cow::from(path.to_string()),
+        _ => panic!("ComputeShader::shader() must return a ShaderRef::Path"),
+    }
+}
+
 // -------------------------------------------------------------
 // Render node
 // Ties the pipeline into the Bevy render pipeline.
@@ -376,72 +723,98 @@ impl FromWorld for MyComputeShaderPipeline {
 // the application's render graph.
 // -------------------------------------------------------------
 
-// State of shader program
-enum MyComputeShaderState {
-    Loading,
-    Init,
-    Update,
-}
-
-struct MyComputeShaderNode {
+struct ComputeNode<T: ComputeShader> {
     view_query: QueryState<&'static ViewUniformOffset, With<ExtractedView>>,
-    state: MyComputeShaderState,
+    // The `ComputePipelineKey` variant we're currently tracking readiness
+    // for. Changing `ComputeShaderKey<T>` resets `ready_up_to`, since a
+    // freshly-specialized pipeline needs to compile from scratch.
+    current_key: ComputePipelineKey,
+    // Pipeline ids for `current_key`, refreshed in `update` each time the key
+    // changes (or on the very first frame).
+    active_pipeline_ids: Vec<CachedComputePipelineId>,
+    // Index into `active_pipeline_ids` of the furthest-along entry point
+    // confirmed compiled so far.
+    ready_up_to: usize,
+    // Set once a pipeline fails to compile, so we stop polling it and only
+    // report the error a single time instead of spamming every frame.
+    errored: bool,
+    // Parallel to `active_pipeline_ids`: whether the entry point at that
+    // index has already dispatched, for entries `T::one_shot_entry_points`
+    // names. Reset to all `false` whenever `active_pipeline_ids` is rebuilt.
+    // `Node::run` only gets `&self`, hence the `RefCell`.
+    one_shot_fired: RefCell<Vec<bool>>,
+    _marker: PhantomData<T>,
 }
 
-impl MyComputeShaderNode {
+impl<T: ComputeShader> ComputeNode<T> {
     pub const IN_VIEW: &'static str = "view";
 
     // Implement new for this struct as we need to setup the query state for the view struct given the render app world object.
     pub fn new(world: &mut World) -> Self {
         Self {
-            state: MyComputeShaderState::Loading,
             view_query: QueryState::new(world),
+            current_key: ComputePipelineKey::NONE,
+            active_pipeline_ids: Vec::new(),
+            ready_up_to: 0,
+            errored: false,
+            one_shot_fired: RefCell::new(Vec::new()),
+            _marker: PhantomData,
         }
     }
 }
 
-impl render_graph::Node for MyComputeShaderNode {
+impl<T: ComputeShader> render_graph::Node for ComputeNode<T> {
     fn input(&self) -> Vec<SlotInfo> {
         vec![SlotInfo::new(Self::IN_VIEW, SlotType::Entity)]
     }
-    
-    // Update function of node, used to update states if the shader asset becomes loaded or has been first run-inited.
+
+    // Update function of node, used to advance `ready_up_to` as entry points finish compiling.
     fn update(&mut self, world: &mut World) {
         // self.view_query.update_archetypes(world);
 
-        let pipeline = world.resource::<MyComputeShaderPipeline>();
+        // Check for a key change *before* the `errored` latch, so switching
+        // to a different (potentially valid) `ComputeShaderKey<T>` variant
+        // always gets a fresh attempt instead of being stuck forever behind
+        // an unrelated variant's compile failure.
+        let key = world.resource::<ComputeShaderKey<T>>().0;
+        if key != self.current_key || self.active_pipeline_ids.is_empty() {
+            self.current_key = key;
+            self.ready_up_to = 0;
+            self.errored = false;
+            world.resource_scope(|world, pipeline_cache: Mut<PipelineCache>| {
+                let mut pipeline = world.resource_mut::<ComputePipeline<T>>();
+                self.active_pipeline_ids = pipeline.pipeline_ids_for(&pipeline_cache, key);
+            });
+            *self.one_shot_fired.borrow_mut() = vec![false; self.active_pipeline_ids.len()];
+        }
+
+        if self.errored {
+            return;
+        }
+
         let pipeline_cache = world.resource::<PipelineCache>();
 
-        // Handle states, we do this to make sure shaders are run when they have been loaded.
-        // Match matches the pattern with the list of scrutinees,
-        // can be used as switch statement or more advanced pattern matching
-        match self.state {
-            MyComputeShaderState::Loading => {
-                // In the loading state we check if the current cached init pipeline matches
-                // the requirements of an Ok one.
-                // This is done by supplying the Ok-enum of CachedPipelineState as a pattern.  (_ is used to wildcard pipeline type)
-                // If it matches with the cached pipeline we query, ie. if the cached pipeline (of our type) is also the Ok value...
-                // ... we change state to to Init.
-                if let CachedPipelineState::Ok(_) =
-                    pipeline_cache.get_compute_pipeline_state(pipeline.init_pipeline_id)
-                // if pipeline_cache.get_compute_pipeline_state(pipeline.init_pipeline_id) == CachedPipelineState::Ok(_)
-                {
-                    self.state = MyComputeShaderState::Init;
-                }
-            }
-            // Keep us in init state until the update pipeline is confirmed loaded as well
-            MyComputeShaderState::Init => {
-                if let CachedPipelineState::Ok(_) =
-                    pipeline_cache.get_compute_pipeline_state(pipeline.update_pipeline_id)
-                {
-                    self.state = MyComputeShaderState::Update;
+        // Walk the entry points in order, same as the old Loading -> Init -> Update
+        // state machine, just generalized over however many entry points `T` has.
+        // With async pipeline compilation a pipeline can sit in `Creating` for
+        // several frames; we only advance on a confirmed `Ok`, and halt for
+        // good (instead of looping forever) on a confirmed `Err`.
+        while self.ready_up_to < self.active_pipeline_ids.len() {
+            match pipeline_cache
+                .get_compute_pipeline_state(self.active_pipeline_ids[self.ready_up_to])
+            {
+                CachedPipelineState::Ok(_) => self.ready_up_to += 1,
+                CachedPipelineState::Err(err) => {
+                    error!("compute pipeline failed to compile: {err}");
+                    self.errored = true;
+                    break;
                 }
+                CachedPipelineState::Queued | CachedPipelineState::Creating(_) => break,
             }
-            MyComputeShaderState::Update => {} // No change from this state
         }
     }
 
-    // Run/Dispatch shaders depending on state of node
+    // Run/Dispatch every entry point confirmed ready so far, in order.
     fn run(
         &self,
         graph: &mut render_graph::RenderGraphContext,
@@ -450,15 +823,25 @@ impl render_graph::Node for MyComputeShaderNode {
     ) -> Result<(), render_graph::NodeRunError> {
 
         let view_entity = graph.get_input_entity(Self::IN_VIEW)?;
-        let bind_group = &world.resource::<MyComputeShaderBindGroup>().0;
-        let pipeline = world.resource::<MyComputeShaderPipeline>();
+        let Some(bind_group) = world.get_resource::<ComputeBindGroup<T>>() else {
+            return Ok(());
+        };
         let pipeline_cache = world.resource::<PipelineCache>();
+        let config = world.resource::<ComputeShaderConfig<T>>();
+
+        // Round up instead of truncating, so resolutions that aren't an
+        // exact multiple of the workgroup size still cover every pixel
+        // instead of silently dropping the remainder along each edge.
+        let workgroups_x =
+            (config.resolution.x + config.workgroup_size - 1) / config.workgroup_size;
+        let workgroups_y =
+            (config.resolution.y + config.workgroup_size - 1) / config.workgroup_size;
 
         let mut pass =
             render_context
                 .command_encoder()
                 .begin_compute_pass(&ComputePassDescriptor {
-                    label: Some("my_compute_pass"),
+                    label: Some("compute_pass"),
                 });
 
         // Find the dynamic offset for the engine's view uniform buffer
@@ -466,30 +849,173 @@ impl render_graph::Node for MyComputeShaderNode {
         else { return Ok(()) };
 
         // Set our bindgroup and also supply the offset for the view uniform
-        pass.set_bind_group(0, bind_group, &[view_uniform_offset.offset]);
-
-        // Select pipeline based on the state
-        match self.state {
-            MyComputeShaderState::Loading => {} // Nothing to run when loading cache...
-            MyComputeShaderState::Init => {
-                /*
-                    // Fetch the init pipeline from the cache
-                    let init_pipeline = pipeline_cache
-                        .get_compute_pipeline(pipeline.init_pipeline_id)
-                        .unwrap();
-                    pass.set_pipeline(init_pipeline);
-                    pass.dispatch_workgroups(SIZE.0 / WORKGROUP_SIZE, SIZE.1 / WORKGROUP_SIZE, 1);
-                */
-            }
-            MyComputeShaderState::Update => {
-                // Fetch the update pipeline from the cache
-                let update_pipeline = pipeline_cache
-                    .get_compute_pipeline(pipeline.update_pipeline_id)
-                    .unwrap();
-                pass.set_pipeline(update_pipeline);
-                pass.dispatch_workgroups(SIZE.0 / WORKGROUP_SIZE, SIZE.1 / WORKGROUP_SIZE, 1);
+        pass.set_bind_group(0, &bind_group.0, &[view_uniform_offset.offset]);
+
+        let mut one_shot_fired = self.one_shot_fired.borrow_mut();
+        for (index, &pipeline_id) in self.active_pipeline_ids[..self.ready_up_to]
+            .iter()
+            .enumerate()
+        {
+            // A one-shot entry point (e.g. a ping-pong buffer's "init" seed
+            // pass) only gets dispatched the first time it's ready - every
+            // later frame it's skipped so it can't clobber what "update"
+            // has since written.
+            if T::one_shot_entry_points().contains(&T::entry_points()[index]) {
+                if one_shot_fired[index] {
+                    continue;
+                }
+                one_shot_fired[index] = true;
             }
+
+            let Some(compute_pipeline) = pipeline_cache.get_compute_pipeline(pipeline_id) else {
+                continue;
+            };
+            pass.set_pipeline(compute_pipeline);
+            pass.dispatch_workgroups(workgroups_x, workgroups_y, 1);
         }
         Ok(())
     }
 }
+
+// ----------------------------------------------------------------------------
+// Our own compute shader, implemented on top of the generic plugin above.
+// ----------------------------------------------------------------------------
+
+// Custom struct for tracking the render target.
+// Holds two storage images instead of one: each frame we read the buffer the
+// *other* one wrote last frame and write into this one, then flip - the
+// standard ping-pong technique feedback kernels (Game-of-Life, fluids, ...)
+// need to avoid racing their own output across workgroups.
+// Derives clone so its internals are deep copied, and ExtractResource in
+// order to be able to extract the images from bevy's main/game "world" to
+// its render "world".
+#[derive(Resource, Clone, ExtractResource)]
+pub struct MyComputeShader {
+    pub textures: [Handle<Image>; 2],
+}
+
+impl MyComputeShader {
+    // Index of the buffer holding the most recently completed update - the
+    // one the presentation material should sample. A compute dispatch that
+    // has run `frame_count` times has alternately written into 1, 0, 1, 0, ...
+    fn written_index(&self, frame_count: u32) -> usize {
+        (frame_count % 2) as usize
+    }
+
+    // Index of the buffer the *next* dispatch will read from (the one most
+    // recently written) and the one it will write into.
+    fn previous_and_next(&self, frame_count: u32) -> (usize, usize) {
+        let next = self.written_index(frame_count);
+        (1 - next, next)
+    }
+}
+
+impl ComputeShader for MyComputeShader {
+    fn shader() -> ShaderRef {
+        "shaders/my_compute_shader.wgsl".into()
+    }
+
+    fn entry_points() -> &'static [&'static str] {
+        &["init", "update"]
+    }
+
+    fn one_shot_entry_points() -> &'static [&'static str] {
+        &["init"]
+    }
+
+    fn bind_group_layout(render_device: &RenderDevice, texture_format: TextureFormat) -> BindGroupLayout {
+        let view_layout = BindGroupLayoutEntry {
+            binding: 0,
+            visibility: ShaderStages::COMPUTE,
+            ty: BindingType::Buffer {
+                ty: BufferBindingType::Uniform,
+                has_dynamic_offset: true,
+                min_binding_size: Some(ViewUniform::min_size()),
+            },
+            count: None,
+        };
+
+        // "previous" - read-only, sampled for neighbor lookups.
+        let previous_layout = BindGroupLayoutEntry {
+            binding: 1,
+            visibility: ShaderStages::COMPUTE,
+            ty: BindingType::StorageTexture {
+                access: StorageTextureAccess::ReadOnly,
+                format: texture_format,
+                view_dimension: TextureViewDimension::D2,
+            },
+            count: None,
+        };
+
+        // "next" - write-only, this dispatch's output.
+        let next_layout = BindGroupLayoutEntry {
+            binding: 2,
+            visibility: ShaderStages::COMPUTE,
+            ty: BindingType::StorageTexture {
+                access: StorageTextureAccess::WriteOnly,
+                format: texture_format,
+                view_dimension: TextureViewDimension::D2,
+            },
+            count: None,
+        };
+
+        // Per-frame uniforms: viewport, aspect ratio and time.
+        let uniform_layout = BindGroupLayoutEntry {
+            binding: 3,
+            visibility: ShaderStages::COMPUTE,
+            ty: BindingType::Buffer {
+                ty: BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: Some(ComputeUniforms::min_size()),
+            },
+            count: None,
+        };
+
+        render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("my_rendertexture_bindgroup_layout"),
+            entries: &[view_layout, previous_layout, next_layout, uniform_layout],
+        })
+    }
+
+    fn bind_group(
+        &self,
+        render_device: &RenderDevice,
+        layout: &BindGroupLayout,
+        gpu_images: &RenderAssets<Image>,
+        view_uniforms: &ViewUniforms,
+        frame_count: u32,
+        compute_uniforms: &ComputeUniformBuffer,
+    ) -> Option<BindGroup> {
+        let view_binding = view_uniforms.uniforms.binding()?;
+        let (previous, next) = self.previous_and_next(frame_count);
+        let previous_view = gpu_images.get(&self.textures[previous])?;
+        let next_view = gpu_images.get(&self.textures[next])?;
+        let uniform_binding = compute_uniforms.0.binding()?;
+
+        let view_entry = BindGroupEntry {
+            binding: 0,
+            resource: view_binding,
+        };
+
+        let previous_entry = BindGroupEntry {
+            binding: 1,
+            resource: BindingResource::TextureView(&previous_view.texture_view),
+        };
+
+        let next_entry = BindGroupEntry {
+            binding: 2,
+            resource: BindingResource::TextureView(&next_view.texture_view),
+        };
+
+        let uniform_entry = BindGroupEntry {
+            binding: 3,
+            resource: uniform_binding,
+        };
+
+        Some(render_device.create_bind_group(&BindGroupDescriptor {
+            label: Some("my_rendertexture_bindgroup"),
+            layout,
+            entries: &[view_entry, previous_entry, next_entry, uniform_entry],
+        }))
+    }
+}